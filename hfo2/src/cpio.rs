@@ -0,0 +1,230 @@
+//! Parser for the "newc" ASCII CPIO format (magic `070701`) used for
+//! Hafnium's boot initramfs, built on top of [`MemIter`].
+//!
+//! Each entry is a fixed 110-byte header of 8-digit hex fields, followed by
+//! the NUL-terminated name padded to a 4-byte boundary, then the file data
+//! also padded to a 4-byte boundary. The archive ends with a `TRAILER!!!`
+//! entry.
+
+// Not yet wired up to a boot-time consumer in this tree; keep the module
+// warning-free until the VM loader starts calling into it.
+#![allow(dead_code)]
+
+use crate::memiter::MemIter;
+
+const MAGIC: &[u8] = b"070701";
+const HEADER_LEN: usize = 110;
+const TRAILER_NAME: &[u8] = b"TRAILER!!!";
+
+/// A single parsed newc entry.
+pub struct CpioEntry<'a> {
+    pub name: &'a [u8],
+    pub mode: u32,
+    pub data: &'a [u8],
+}
+
+/// Iterates over the entries of a newc CPIO archive.
+///
+/// Iteration stops, yielding `None`, once the `TRAILER!!!` entry is reached,
+/// the magic doesn't match, or the input is truncated partway through an
+/// entry -- a malformed archive ends iteration rather than panicking.
+pub struct CpioIter<'a> {
+    cursor: MemIter<'a>,
+    done: bool,
+}
+
+impl<'a> CpioIter<'a> {
+    /// Creates an iterator over the newc archive in `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            cursor: MemIter::new(data),
+            done: false,
+        }
+    }
+}
+
+/// Parses an 8-digit hex field, as used for every numeric newc header field.
+fn parse_hex8(field: &[u8]) -> Option<u32> {
+    if field.len() != 8 {
+        return None;
+    }
+    let mut value: u32 = 0;
+    for &b in field {
+        let digit = match b {
+            b'0'..=b'9' => b - b'0',
+            b'a'..=b'f' => b - b'a' + 10,
+            b'A'..=b'F' => b - b'A' + 10,
+            _ => return None,
+        };
+        value = (value << 4) | u32::from(digit);
+    }
+    Some(value)
+}
+
+/// Rounds `len` up to the next multiple of 4, as newc pads names and file
+/// data to 4-byte boundaries.
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+impl<'a> Iterator for CpioIter<'a> {
+    type Item = CpioEntry<'a>;
+
+    fn next(&mut self) -> Option<CpioEntry<'a>> {
+        if self.done {
+            return None;
+        }
+
+        let entry = self.parse_entry();
+        if entry.is_none() {
+            self.done = true;
+        }
+        entry
+    }
+}
+
+impl<'a> CpioIter<'a> {
+    /// Parses the entry under the cursor, returning `None` at the trailer or
+    /// on any malformed or truncated input.
+    fn parse_entry(&mut self) -> Option<CpioEntry<'a>> {
+        let header = self.cursor.take(HEADER_LEN)?;
+        if &header[0..6] != MAGIC {
+            self.done = true;
+            return None;
+        }
+
+        let mode = parse_hex8(&header[14..22])?;
+        let filesize = parse_hex8(&header[54..62])? as usize;
+        let namesize = parse_hex8(&header[94..102])? as usize;
+        if namesize == 0 {
+            self.done = true;
+            return None;
+        }
+
+        // Name includes its NUL terminator; header + name is padded to a
+        // 4-byte boundary before the file data starts.
+        let name = self.cursor.take(namesize)?;
+        let name_pad = align4(HEADER_LEN + namesize) - (HEADER_LEN + namesize);
+        self.cursor.advance(name_pad)?;
+
+        let data = self.cursor.take(filesize)?;
+        let data_pad = align4(filesize) - filesize;
+        self.cursor.advance(data_pad)?;
+
+        // `split` on a byte slice always yields at least one element.
+        let name = name.split(|&b| b == 0).next().unwrap();
+        if name == TRAILER_NAME {
+            self.done = true;
+            return None;
+        }
+
+        Some(CpioEntry { name, mode, data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Appends one newc header, its NUL-terminated `name`, and `data`, each
+    /// padded out to the 4-byte boundary the format requires.
+    fn push_entry(buf: &mut ::std::vec::Vec<u8>, mode: u32, name: &[u8], data: &[u8]) {
+        let mut name = name.to_vec();
+        name.push(0);
+
+        buf.extend_from_slice(MAGIC);
+        for field in [
+            0,               // ino
+            mode,            // mode
+            0,               // uid
+            0,               // gid
+            1,               // nlink
+            0,               // mtime
+            data.len() as u32, // filesize
+            0,               // devmajor
+            0,               // devminor
+            0,               // rdevmajor
+            0,               // rdevminor
+            name.len() as u32, // namesize
+            0,               // check
+        ] {
+            buf.extend_from_slice(format!("{:08x}", field).as_bytes());
+        }
+
+        buf.extend_from_slice(&name);
+        while !buf.len().is_multiple_of(4) {
+            buf.push(0);
+        }
+
+        buf.extend_from_slice(data);
+        while !buf.len().is_multiple_of(4) {
+            buf.push(0);
+        }
+    }
+
+    fn push_trailer(buf: &mut ::std::vec::Vec<u8>) {
+        push_entry(buf, 0, b"TRAILER!!!", b"");
+    }
+
+    #[test]
+    fn parses_multiple_entries_with_unaligned_lengths() {
+        let mut buf = ::std::vec::Vec::new();
+        // "afile" (6 bytes with NUL) and 3 bytes of data: the 110-byte header
+        // plus the name ends at 116, already aligned, so only the data needs
+        // padding; padding the name on its own length would skip 2 bytes.
+        push_entry(&mut buf, 0o100644, b"afile", b"xyz");
+        // "b" (2 bytes with NUL) and 5 bytes of data: header plus name ends at
+        // 112, again aligned, and the data needs a different amount of padding.
+        push_entry(&mut buf, 0o100755, b"b", b"hello");
+        push_trailer(&mut buf);
+
+        let mut entries = CpioIter::new(&buf);
+
+        let first = entries.next().expect("first entry");
+        assert_eq!(first.name, b"afile");
+        assert_eq!(first.mode, 0o100644);
+        assert_eq!(first.data, b"xyz");
+
+        let second = entries.next().expect("second entry");
+        assert_eq!(second.name, b"b");
+        assert_eq!(second.mode, 0o100755);
+        assert_eq!(second.data, b"hello");
+
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn stops_at_trailer_without_yielding_it() {
+        let mut buf = ::std::vec::Vec::new();
+        push_trailer(&mut buf);
+
+        let mut entries = CpioIter::new(&buf);
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn empty_buffer_yields_nothing() {
+        let mut entries = CpioIter::new(&[]);
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn bad_magic_yields_nothing() {
+        let mut buf = ::std::vec![0u8; HEADER_LEN];
+        buf[0..6].copy_from_slice(b"070700");
+
+        let mut entries = CpioIter::new(&buf);
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn truncated_entry_yields_nothing() {
+        let mut buf = ::std::vec::Vec::new();
+        // A header promising 100 bytes of data, but only 50 actually present.
+        push_entry(&mut buf, 0o100644, b"afile", &[0u8; 100]);
+        buf.truncate(buf.len() - 50);
+
+        let mut entries = CpioIter::new(&buf);
+        assert!(entries.next().is_none());
+    }
+}
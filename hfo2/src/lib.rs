@@ -1,10 +1,22 @@
-#![no_std]
+// `std` is only linked for unit tests, which run on the host rather than
+// under `#![no_std]`; the crate itself (and its own `#[panic_handler]`) stay
+// `no_std` otherwise, since that's what `std` is already in this crate.
+#![cfg_attr(not(test), no_std)]
 
+#[cfg(not(test))]
+use core::fmt::Write;
+#[cfg(not(test))]
 use core::panic::PanicInfo;
 
+#[cfg(not(test))]
+use crate::std::{halt, FmtSink, PlatformConsole};
+
+#[cfg(not(test))]
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    loop {}
+fn panic(info: &PanicInfo) -> ! {
+    let mut console = PlatformConsole;
+    let _ = write!(FmtSink(&mut console), "{}", info);
+    halt()
 }
 
 mod cpio;
@@ -0,0 +1,53 @@
+//! A forward-only cursor over an in-memory byte range, the Rust counterpart
+//! of Hafnium's `struct memiter`. It underlies parsers (such as [`cpio`])
+//! that need to walk a fixed buffer without ever allocating.
+//!
+//! [`cpio`]: crate::cpio
+
+// Not yet wired up to a boot-time consumer in this tree; keep the module
+// warning-free until the VM loader starts calling into it.
+#![allow(dead_code)]
+
+/// A cursor over a byte slice. Bytes are consumed from the front as callers
+/// `take` or `advance` past them; nothing is ever copied.
+#[derive(Clone, Copy)]
+pub struct MemIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MemIter<'a> {
+    /// Creates a cursor over the whole of `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns whether the cursor has no bytes left.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the number of bytes left under the cursor.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the next `len` bytes without consuming them, or `None` if
+    /// fewer than `len` bytes are left.
+    pub fn peek(&self, len: usize) -> Option<&'a [u8]> {
+        self.data.get(..len)
+    }
+
+    /// Consumes and returns the next `len` bytes, or `None` (leaving the
+    /// cursor unchanged) if fewer than `len` bytes are left.
+    pub fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.peek(len)?;
+        self.data = &self.data[len..];
+        Some(bytes)
+    }
+
+    /// Skips `len` bytes, or returns `None` (leaving the cursor unchanged)
+    /// if fewer than `len` bytes are left.
+    pub fn advance(&mut self, len: usize) -> Option<()> {
+        self.take(len).map(|_| ())
+    }
+}
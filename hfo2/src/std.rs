@@ -0,0 +1,92 @@
+//! Minimal `no_std` support shims that would otherwise come from `std`,
+//! namely somewhere to send diagnostic output when there is no OS underneath
+//! us to catch it.
+//!
+//! Irrelevant to unit tests, which run on the host against real `std`.
+#![cfg(not(test))]
+
+use core::fmt;
+
+extern "C" {
+    fn plat_console_putchar(c: u8);
+}
+
+/// A destination for early diagnostic output, such as a formatted panic
+/// message. Implementations must not allocate, since a sink has to keep
+/// working even when the panic happened inside the allocator.
+pub trait PanicSink {
+    fn write_str(&mut self, s: &str);
+}
+
+/// `PanicSink` backed by Hafnium's platform console (`plat_console_putchar`),
+/// i.e. whatever serial/HVC transport the running platform has wired up.
+pub struct PlatformConsole;
+
+impl PanicSink for PlatformConsole {
+    fn write_str(&mut self, s: &str) {
+        for b in s.bytes() {
+            unsafe {
+                plat_console_putchar(b);
+            }
+        }
+    }
+}
+
+/// Adapts a `PanicSink` to `core::fmt::Write`, so a `PanicInfo` can be
+/// rendered into it with `write!`.
+pub struct FmtSink<'a, S: PanicSink>(pub &'a mut S);
+
+impl<'a, S: PanicSink> fmt::Write for FmtSink<'a, S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s);
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "wfi", feature = "trap"))]
+compile_error!("features \"wfi\" and \"trap\" are mutually exclusive halt strategies");
+
+/// Halts the current core after a fault has been reported. Which of the
+/// following this does is picked by feature flag, so the same panic path
+/// works unattended in production and under a debugger:
+///
+/// - `spin` (default): busy-loop, hinting to the CPU that it's spinning.
+/// - `wfi`: idle the core with `wfi` instead of busy-spinning.
+/// - `trap`: execute a breakpoint/undefined-instruction trap so an attached
+///   debugger stops exactly at the panic site.
+pub fn halt() -> ! {
+    loop {
+        #[cfg(feature = "trap")]
+        trap_instruction();
+
+        #[cfg(feature = "wfi")]
+        wfi_instruction();
+
+        #[cfg(not(any(feature = "wfi", feature = "trap")))]
+        core::hint::spin_loop();
+    }
+}
+
+#[cfg(feature = "trap")]
+fn trap_instruction() {
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        core::arch::asm!("brk #0");
+    }
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        core::arch::asm!("ud2");
+    }
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86_64")))]
+    compile_error!("\"trap\" halt strategy is not implemented for this architecture");
+}
+
+#[cfg(feature = "wfi")]
+fn wfi_instruction() {
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        core::arch::asm!("wfi");
+    }
+    #[cfg(not(target_arch = "aarch64"))]
+    compile_error!("\"wfi\" halt strategy is not implemented for this architecture");
+}
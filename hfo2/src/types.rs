@@ -0,0 +1 @@
+//! Shared type definitions used across the crate.